@@ -0,0 +1,36 @@
+use std::{fs, path::Path};
+
+use crate::GenericResult;
+
+/// a single filesystem entry discovered while walking the workspace,
+/// abstracting over the two walker backends (globwalk vs. the `ignore` crate)
+pub(crate) enum DirEntry {
+    Glob(globwalk::DirEntry),
+    Ignore(ignore::DirEntry),
+}
+
+impl DirEntry {
+    pub(crate) fn path(&self) -> &Path {
+        match self {
+            DirEntry::Glob(entry) => entry.path(),
+            DirEntry::Ignore(entry) => entry.path(),
+        }
+    }
+
+    pub(crate) fn file_type(&self) -> GenericResult<fs::FileType> {
+        match self {
+            DirEntry::Glob(entry) => Ok(entry.file_type()),
+            DirEntry::Ignore(entry) => match entry.file_type() {
+                Some(file_type) => Ok(file_type),
+                None => Ok(self.path().metadata()?.file_type()),
+            },
+        }
+    }
+
+    pub(crate) fn metadata(&self) -> GenericResult<fs::Metadata> {
+        match self {
+            DirEntry::Glob(entry) => Ok(entry.metadata()?),
+            DirEntry::Ignore(entry) => Ok(entry.metadata()?),
+        }
+    }
+}
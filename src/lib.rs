@@ -9,28 +9,44 @@
 
 use cargo_metadata::{Message, MetadataCommand};
 use color_eyre::eyre::{Context, Result};
+use entry::DirEntry;
 use filetime::{set_symlink_file_times, FileTime};
-use globwalk::DirEntry;
+use rayon::prelude::*;
 use serde::Deserialize;
 use std::{
+    collections::{HashMap, HashSet},
     env,
     error::Error,
     ffi::OsStr,
     fs,
     path::{Path, PathBuf},
     process::{Command, Stdio},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
     vec,
 };
 
 mod check;
+mod entry;
 mod paths;
 mod progress;
+mod sync;
 
 type GenericResult<T> = Result<T, Box<dyn Error>>;
 pub type NullResult = GenericResult<()>;
 
 const SKIPPABLES: [&str; 4] = ["wargo", "cargo-wsl", "cargo", "wsl"];
 
+/// cargo subcommands whose compilation artifacts are harvested from a
+/// `--message-format=json` stream: `build` is parsed directly, the others
+/// through a separate probe build (see `probe_build_args`)
+const JSON_STREAM_COMMANDS: [&str; 4] = ["build", "test", "bench", "run"];
+
+/// artifact target kinds copied back by default when `artifact_kinds` is unset
+const DEFAULT_ARTIFACT_KINDS: [&str; 5] = ["bin", "cdylib", "staticlib", "example", "test"];
+
 const HELP_TEXT: &str = r#"wargo
 
 cargo's evil twin to work with projects in the twilight zone of WSL2
@@ -70,11 +86,50 @@ struct WargoConfig {
     /// (will remove and recreate folder)
     clean: bool,
 
+    /// walk the workspace with the `ignore` crate so `.gitignore`, `.ignore`,
+    /// and nested per-directory ignore files are honored instead of copying
+    /// everything the fixed globwalk patterns allow through
+    /// default: true
+    respect_gitignore: Option<bool>,
+
+    /// allow-list of `cargo_metadata` artifact target kinds to copy back to
+    /// the workspace
+    /// default: ["bin", "cdylib", "staticlib", "example", "test"]
+    artifact_kinds: Option<Vec<String>>,
+
+    /// number of worker threads used to copy files in parallel
+    /// default: number of available CPUs
+    copy_workers: Option<usize>,
+
+    /// command aliases, expanded before dispatching to cargo
+    /// (same idea as cargo's own `[alias]` config table)
+    aliases: Option<HashMap<String, AliasExpansion>>,
+
     /// internal option
     #[serde(skip)]
     clean_git: bool,
 }
 
+/// a Wargo.toml alias may expand to a single space-separated string
+/// (à la cargo) or an explicit argument list
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+enum AliasExpansion {
+    Single(String),
+    Multiple(Vec<String>),
+}
+
+impl AliasExpansion {
+    fn into_args(self) -> Vec<String> {
+        match self {
+            AliasExpansion::Single(command) => {
+                command.split_whitespace().map(str::to_string).collect()
+            }
+            AliasExpansion::Multiple(args) => args,
+        }
+    }
+}
+
 pub fn run(_from: &str) -> NullResult {
     color_eyre::install()?;
     check::wsl2_or_exit()?;
@@ -92,12 +147,13 @@ pub fn run(_from: &str) -> NullResult {
         .into_std_path_buf()
         .canonicalize()?;
     let mut wargo_config = get_wargo_config(&workspace_root)?;
+    let args = expand_aliases(&wargo_config, args)?;
     let dest_dir = get_destination_dir(&wargo_config, &workspace_root);
 
     let entries = collect_entries(&mut wargo_config, &workspace_root)?;
     copy_files(entries, &wargo_config, &workspace_root, &dest_dir)?;
 
-    let artifacts = exec_cargo_command(&dest_dir, &workspace_root, args)?;
+    let artifacts = exec_cargo_command(&dest_dir, &workspace_root, &wargo_config, args)?;
     copy_artifacts(&dest_dir, &workspace_root, artifacts)?;
 
     Ok(())
@@ -116,6 +172,29 @@ fn parse_args() -> Vec<String> {
     args
 }
 
+/// recursively splice `[aliases]` expansions in for the first argument,
+/// guarding against cycles (e.g. an alias expanding to itself)
+fn expand_aliases(wargo_config: &WargoConfig, args: Vec<String>) -> GenericResult<Vec<String>> {
+    let Some(aliases) = &wargo_config.aliases else {
+        return Ok(args);
+    };
+
+    let mut args = args;
+    let mut seen = HashSet::new();
+
+    while let Some(name) = args.first() {
+        let Some(expansion) = aliases.get(name) else {
+            break;
+        };
+        if !seen.insert(name.clone()) {
+            return Err(format!("Wargo.toml alias cycle detected for `{name}`").into());
+        }
+        args.splice(0..1, expansion.clone().into_args());
+    }
+
+    Ok(args)
+}
+
 fn get_wargo_config<P>(workspace_root: &P) -> GenericResult<WargoConfig>
 where
     P: AsRef<Path>,
@@ -169,37 +248,52 @@ fn collect_entries<P>(
 where
     P: AsRef<Path>,
 {
-    let mut patterns = vec!["**"];
-
     // migration phase (v0.2) - remove ignore_* blocks and de-optionize with v0.3
 
-    if let Some(include_git) = wargo_config.include_git {
-        if !include_git {
-            patterns.push("!.git");
-        } else {
+    let include_git = if let Some(include_git) = wargo_config.include_git {
+        if include_git {
             wargo_config.clean_git = true;
         }
+        include_git
     } else if let Some(ignore_git) = wargo_config.ignore_git {
-        if ignore_git {
-            patterns.push("!.git");
-        } else {
+        if !ignore_git {
             wargo_config.clean_git = true;
         }
+        !ignore_git
     } else {
         // default if no option was provided
-        patterns.push("!.git");
-    }
+        false
+    };
 
-    if let Some(include_target) = wargo_config.include_target {
-        if !include_target {
-            patterns.push("!target");
-        }
+    let include_target = if let Some(include_target) = wargo_config.include_target {
+        include_target
     } else if let Some(ignore_target) = wargo_config.ignore_target {
-        if ignore_target {
-            patterns.push("!target");
-        }
+        !ignore_target
     } else {
         // default if no option was provided
+        false
+    };
+
+    if wargo_config.respect_gitignore.unwrap_or(true) {
+        collect_entries_ignore(workspace_root, include_git, include_target)
+    } else {
+        collect_entries_globwalk(workspace_root, include_git, include_target)
+    }
+}
+
+fn collect_entries_globwalk<P>(
+    workspace_root: &P,
+    include_git: bool,
+    include_target: bool,
+) -> GenericResult<Vec<DirEntry>>
+where
+    P: AsRef<Path>,
+{
+    let mut patterns = vec!["**"];
+    if !include_git {
+        patterns.push("!.git");
+    }
+    if !include_target {
         patterns.push("!target");
     }
 
@@ -209,10 +303,46 @@ where
             .build()?
             .into_iter()
             .filter_map(Result::ok)
+            .map(DirEntry::Glob)
             .collect();
     Ok(entries)
 }
 
+fn collect_entries_ignore<P>(
+    workspace_root: &P,
+    include_git: bool,
+    include_target: bool,
+) -> GenericResult<Vec<DirEntry>>
+where
+    P: AsRef<Path>,
+{
+    let root = workspace_root.as_ref().to_path_buf();
+
+    let entries: Vec<DirEntry> = ignore::WalkBuilder::new(&root)
+        // standard_filters() overrides hidden()/parents()/ignore()/git_*(),
+        // so it must come first or it silently re-enables hidden-file
+        // skipping and `filter_entry` never sees `.git`/dotfiles at all
+        .standard_filters(true)
+        .hidden(false)
+        .filter_entry(move |candidate| {
+            let rel = match candidate.path().strip_prefix(&root) {
+                Ok(rel) => rel,
+                Err(_) => return true,
+            };
+            match rel.components().next() {
+                Some(first) if first.as_os_str() == ".git" => include_git,
+                Some(first) if first.as_os_str() == "target" => include_target,
+                _ => true,
+            }
+        })
+        .build()
+        .filter_map(Result::ok)
+        .filter(|candidate| candidate.depth() > 0)
+        .map(DirEntry::Ignore)
+        .collect();
+    Ok(entries)
+}
+
 fn copy_files<P>(
     entries: Vec<DirEntry>,
     wargo_config: &WargoConfig,
@@ -220,7 +350,7 @@ fn copy_files<P>(
     dest_dir: &P,
 ) -> NullResult
 where
-    P: AsRef<Path>,
+    P: AsRef<Path> + Sync,
 {
     if wargo_config.clean && dest_dir.as_ref().exists() {
         fs::remove_dir_all(&dest_dir).context("dest_dir cleaning failed")?;
@@ -233,42 +363,134 @@ where
         fs::remove_dir_all(&git_dir).context("dest_dir/.git cleaning failed")?;
     }
 
-    let bar = progress::bar(entries.len() as u64);
-    for entry in bar.wrap_iter(entries.iter()) {
-        let is_dir = entry.file_type().is_dir();
+    let index_path = sync::index_path(dest_dir);
+    let previous_index = sync::load(&index_path);
+    let next_index = Mutex::new(sync::Index::default());
+
+    // directories must exist before the files inside them can be written,
+    // so create them up front, in order, before fanning the copies out
+    let mut dirs: Vec<DirEntry> = Vec::new();
+    let mut files: Vec<DirEntry> = Vec::new();
+    for entry in entries {
+        if entry.file_type()?.is_dir() {
+            dirs.push(entry);
+        } else {
+            files.push(entry);
+        }
+    }
+
+    for entry in &dirs {
         let src_path = entry.path();
         let prj_path = src_path.strip_prefix(workspace_root)?;
-        let dst_path = &dest_dir.as_ref().to_path_buf().join(prj_path);
+        let dst_path = dest_dir.as_ref().join(prj_path);
+        fs::create_dir_all(&dst_path).context("Directory creation failed")?;
+        next_index
+            .lock()
+            .unwrap()
+            .entries
+            .insert(prj_path.to_string_lossy().into_owned(), sync::IndexEntry::Dir);
+    }
 
-        let metadata = entry.metadata()?;
-        let mtime = FileTime::from_last_modification_time(&metadata);
-        let atime = FileTime::from_last_access_time(&metadata);
+    let worker_count = wargo_config.copy_workers.unwrap_or_else(|| {
+        std::thread::available_parallelism()
+            .map(std::num::NonZeroUsize::get)
+            .unwrap_or(1)
+    });
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(worker_count)
+        .build()
+        .context("Building copy worker pool failed")?;
+
+    let copied = AtomicU64::new(0);
+    let skipped = AtomicU64::new(0);
+
+    let bar = progress::bar(files.len() as u64);
+    let copy_result = pool.install(|| {
+        files.par_iter().try_for_each(|entry| -> NullResult {
+            let src_path = entry.path();
+            let prj_path = src_path.strip_prefix(workspace_root)?;
+            let dst_path = dest_dir.as_ref().join(prj_path);
+            let rel_key = prj_path.to_string_lossy().into_owned();
+
+            let metadata = entry.metadata()?;
+            let mtime = FileTime::from_last_modification_time(&metadata);
+            let atime = FileTime::from_last_access_time(&metadata);
+            let size = metadata.len();
+
+            let unchanged = dst_path.exists()
+                && matches!(
+                    previous_index.entries.get(&rel_key),
+                    Some(sync::IndexEntry::File { size: prev_size, mtime_secs, mtime_nanos })
+                        if *prev_size == size
+                            && *mtime_secs == mtime.unix_seconds()
+                            && *mtime_nanos == mtime.nanoseconds()
+                );
+
+            if unchanged {
+                skipped.fetch_add(1, Ordering::Relaxed);
+            } else {
+                fs::copy(src_path, &dst_path).with_context(|| {
+                    format!(
+                        "Copying failed: {} -> {}",
+                        &src_path.display(),
+                        &dst_path.display()
+                    )
+                })?;
+                set_symlink_file_times(&dst_path, atime, mtime).with_context(|| {
+                    format!("Setting file timestamps failed for {}", &dst_path.display())
+                })?;
+                copied.fetch_add(1, Ordering::Relaxed);
+            }
 
-        if is_dir {
-            fs::create_dir_all(dst_path).context("Directory creation failed")?;
-        } else {
-            // TODO(maybe): should skip if file is unchanged;
-            // OTOH it would mean more FS calls/checks
-            fs::copy(src_path, dst_path).with_context(|| {
-                format!(
-                    "Copying failed: {} -> {}",
-                    &src_path.display(),
-                    &dst_path.display()
-                )
-            })?;
-        }
+            next_index.lock().unwrap().entries.insert(
+                rel_key,
+                sync::IndexEntry::File {
+                    size,
+                    mtime_secs: mtime.unix_seconds(),
+                    mtime_nanos: mtime.nanoseconds(),
+                },
+            );
+
+            bar.inc(1);
+            Ok(())
+        })
+    });
+
+    bar.finish_with_message(format!(
+        "Files copied: {}, skipped (unchanged): {}",
+        copied.load(Ordering::Relaxed),
+        skipped.load(Ordering::Relaxed)
+    ));
+    copy_result?;
 
-        set_symlink_file_times(dst_path, atime, mtime).with_context(|| {
-            format!("Setting file timestamps failed for {}", &dst_path.display())
-        })?;
+    let next_index = next_index.into_inner().unwrap();
+
+    // prune destination entries whose source no longer exists
+    for (rel_key, prev_entry) in &previous_index.entries {
+        if next_index.entries.contains_key(rel_key) {
+            continue;
+        }
+        let stale_path = dest_dir.as_ref().join(rel_key);
+        if !stale_path.exists() {
+            continue;
+        }
+        match prev_entry {
+            sync::IndexEntry::Dir => fs::remove_dir_all(&stale_path)
+                .with_context(|| format!("Pruning stale dir failed: {}", stale_path.display()))?,
+            sync::IndexEntry::File { .. } => fs::remove_file(&stale_path)
+                .with_context(|| format!("Pruning stale file failed: {}", stale_path.display()))?,
+        }
     }
-    bar.finish_with_message("Files copied");
+
+    sync::save_atomic(&index_path, &next_index)?;
+
     Ok(())
 }
 
 fn exec_cargo_command<P>(
     dest_dir: &P,
     workspace_root: &P,
+    wargo_config: &WargoConfig,
     args: Vec<String>,
 ) -> GenericResult<Vec<PathBuf>>
 where
@@ -283,29 +505,35 @@ where
 
     let mut files: Vec<PathBuf> = Vec::new();
 
-    let mut cargo_args = args;
+    let cargo_args = args;
     if let Some(arg) = cargo_args.first() {
-        // special case: cargo build -> use JSON output
-        // so we can retrieve and parse the compilation artifacts
         if arg == "build" {
-            cargo_args.insert(1, "--message-format=json-render-diagnostics".into());
+            // build is itself the JSON-streamed command: a single pass both
+            // builds and harvests the resulting artifacts
+            let mut json_args = cargo_args;
+            json_args.insert(1, "--message-format=json-render-diagnostics".into());
+            files.extend(run_with_json_artifacts(
+                &exec_dest,
+                json_args,
+                &artifact_kinds(wargo_config),
+            )?);
+        } else if JSON_STREAM_COMMANDS.contains(&arg.as_str()) {
+            // test/bench/run don't print pure JSON on stdout themselves (the
+            // test harness or the executed binary writes plain text after
+            // the compiler's JSON lines), so harvest artifacts with a
+            // separate, JSON-captured probe build first, then invoke the
+            // real command with inherited stdio
+            let probe_args = probe_build_args(arg, &cargo_args);
+            files.extend(run_with_json_artifacts(
+                &exec_dest,
+                probe_args,
+                &artifact_kinds(wargo_config),
+            )?);
 
             let mut cmd = Command::new("cargo")
                 .args(cargo_args)
                 .current_dir(&exec_dest)
-                .stdout(Stdio::piped())
                 .spawn()?;
-
-            let reader = std::io::BufReader::new(cmd.stdout.take().expect("no stdout captured"));
-            for message in Message::parse_stream(reader) {
-                if let Message::CompilerArtifact(artifact) = message.unwrap() {
-                    if artifact.target.kind[0] == "bin" {
-                        for filename in artifact.filenames {
-                            files.push(filename.into_std_path_buf())
-                        }
-                    }
-                }
-            }
             cmd.wait()?;
         } else {
             let mut cmd = Command::new("cargo")
@@ -318,6 +546,73 @@ where
     Ok(files)
 }
 
+fn artifact_kinds(wargo_config: &WargoConfig) -> Vec<String> {
+    wargo_config
+        .artifact_kinds
+        .clone()
+        .unwrap_or_else(|| DEFAULT_ARTIFACT_KINDS.iter().map(|kind| kind.to_string()).collect())
+}
+
+/// arguments for the `cargo build` probe pass that harvests artifacts ahead
+/// of running `subcommand`; shares whatever flags precede a `--` separator
+/// (those are cargo-level, not harness/binary arguments) and asks for the
+/// equivalent build outputs (`--tests`/`--benches`)
+fn probe_build_args(subcommand: &str, cargo_args: &[String]) -> Vec<String> {
+    let shared_flags = cargo_args
+        .iter()
+        .skip(1)
+        .take_while(|arg| arg.as_str() != "--");
+
+    let mut probe_args = vec!["build".to_string()];
+    probe_args.extend(shared_flags.cloned());
+    match subcommand {
+        "test" => probe_args.push("--tests".into()),
+        "bench" => probe_args.push("--benches".into()),
+        _ => {}
+    }
+    probe_args.insert(1, "--message-format=json-render-diagnostics".into());
+    probe_args
+}
+
+/// run `cargo` with the given (already `--message-format=json`-flagged)
+/// arguments and collect the filenames of artifacts matching `artifact_kinds`
+fn run_with_json_artifacts<P>(
+    exec_dest: &P,
+    cargo_args: Vec<String>,
+    artifact_kinds: &[String],
+) -> GenericResult<Vec<PathBuf>>
+where
+    P: AsRef<Path>,
+{
+    let mut files: Vec<PathBuf> = Vec::new();
+
+    let mut cmd = Command::new("cargo")
+        .args(cargo_args)
+        .current_dir(exec_dest)
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let reader = std::io::BufReader::new(cmd.stdout.take().expect("no stdout captured"));
+    for message in Message::parse_stream(reader) {
+        let message = message.context("Failed to parse cargo JSON output")?;
+        if let Message::CompilerArtifact(artifact) = message {
+            if artifact
+                .target
+                .kind
+                .iter()
+                .any(|kind| artifact_kinds.iter().any(|allowed| allowed == kind))
+            {
+                for filename in artifact.filenames {
+                    files.push(filename.into_std_path_buf())
+                }
+            }
+        }
+    }
+    cmd.wait()?;
+
+    Ok(files)
+}
+
 fn copy_artifacts<P>(dest_dir: &P, workspace_root: &P, artifacts: Vec<PathBuf>) -> NullResult
 where
     P: AsRef<Path>,
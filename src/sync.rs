@@ -0,0 +1,75 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use crate::NullResult;
+
+/// prefix/suffix of the sync index filename; kept as a sibling of the
+/// destination project dir so it survives a `clean` wipe of the project dir
+/// itself, and named after that dir so projects sharing a `dest_base_dir`
+/// (the default) don't clobber each other's index
+const INDEX_FILE_PREFIX: &str = ".wargo-sync-";
+const INDEX_FILE_SUFFIX: &str = ".json";
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub(crate) enum IndexEntry {
+    File {
+        size: u64,
+        mtime_secs: i64,
+        mtime_nanos: u32,
+    },
+    Dir,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub(crate) struct Index {
+    pub(crate) entries: HashMap<String, IndexEntry>,
+}
+
+pub(crate) fn index_path<P>(dest_dir: &P) -> PathBuf
+where
+    P: AsRef<Path>,
+{
+    let dest_dir = dest_dir.as_ref();
+    let project_name = dest_dir
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+
+    dest_dir
+        .parent()
+        .unwrap_or(dest_dir)
+        .join(format!("{INDEX_FILE_PREFIX}{project_name}{INDEX_FILE_SUFFIX}"))
+}
+
+/// a missing or unreadable index is treated as "first run", not an error
+pub(crate) fn load<P>(path: &P) -> Index
+where
+    P: AsRef<Path>,
+{
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+pub(crate) fn save_atomic<P>(path: &P, index: &Index) -> NullResult
+where
+    P: AsRef<Path>,
+{
+    let path = path.as_ref();
+    let tmp_path = path.with_extension("json.tmp");
+    let serialized = serde_json::to_string_pretty(index)?;
+
+    let mut file = fs::File::create(&tmp_path)?;
+    file.write_all(serialized.as_bytes())?;
+    file.sync_all()?;
+    fs::rename(&tmp_path, path)?;
+
+    Ok(())
+}